@@ -35,6 +35,7 @@ mod polka_agents {
         Pending,
         Completed,
         Failed,
+        Disputed,
     }
 
     #[derive(Debug, scale::Encode, scale::Decode, PartialEq, Eq, Clone)]
@@ -48,6 +49,9 @@ mod polka_agents {
         timestamp: u64,
         status: InteractionStatus,
         fee_paid: Balance,
+        // Block timestamp by which a response must be submitted before the
+        // user may raise a dispute
+        response_deadline: u64,
     }
 
     #[derive(Debug, scale::Encode, scale::Decode, PartialEq, Eq)]
@@ -61,6 +65,15 @@ mod polka_agents {
         InteractionNotFound,
         InvalidStakeAmount,
         InvalidFeePercentage,
+        InvalidCommission,
+        InvalidDelegationAmount,
+        TooManyDelegators,
+        DelegationNotFound,
+        InsufficientDelegation,
+        StakeCoolingDown,
+        UnauthorizedUser,
+        InteractionNotPending,
+        DisputeWindowNotElapsed,
     }
 
     #[ink(storage)]
@@ -79,12 +92,101 @@ mod polka_agents {
         
         // Agent interactions
         agent_interactions: Mapping<AgentId, Vec<InteractionId>>,
-        
+
         // Fee configuration
         platform_fee_percentage: u8,
-        
+
         // Platform owner
         owner: AccountId,
+
+        // Per-delegator stake backing an agent
+        delegations: Mapping<(AgentId, AccountId), Balance>,
+
+        // Delegators backing a given agent, for pro-rata reward distribution
+        agent_delegators: Mapping<AgentId, Vec<AccountId>>,
+
+        // Accumulated platform fees awaiting redemption by staked agents
+        reward_pool: Balance,
+
+        // Sum of credits*stake points currently outstanding across all agents
+        total_points: u128,
+
+        // Sum of credits currently outstanding across all agents
+        total_credits: u64,
+
+        // Cluster-wide stake currently warming up (not yet fully effective)
+        total_activating_stake: Balance,
+
+        // Cluster-wide stake currently cooling down (not yet withdrawable)
+        total_deactivating_stake: Balance,
+
+        // Bounded per-epoch history of cluster-wide stake totals, used to
+        // replay the warmup/cooldown schedule for a given agent
+        stake_history: Vec<StakeHistoryEntry>,
+
+        // Time (ms) a user must wait after a query before a `Pending`
+        // interaction becomes disputable
+        response_window_ms: u64,
+
+        // Share (0-100) of an agent's stake slashed into the reward pool
+        // when a dispute is raised against it
+        slash_penalty_percentage: u8,
+
+        // Lifetime rewards paid out to each delegator (paid immediately on
+        // each query, so this also doubles as an earnings ledger)
+        delegator_rewards: Mapping<(AgentId, AccountId), Balance>,
+    }
+
+    /// Length of an epoch in milliseconds, used to derive the epoch counter
+    /// from the block timestamp
+    const EPOCH_DURATION_MS: u64 = 24 * 60 * 60 * 1000;
+
+    /// Sentinel `deactivation_epoch` meaning "not deactivating"
+    const NOT_DEACTIVATING: u64 = u64::MAX;
+
+    /// Minimum stake: checked against the raw stake to register and to stay
+    /// active after a refund or slash, and against the effective (warmed-up)
+    /// stake for an agent to accept queries
+    const MIN_STAKE_AMOUNT: Balance = 10;
+
+    /// Minimum delegation (and top-up) amount, preventing dust delegations
+    /// from padding `agent_delegators` for free
+    const MIN_DELEGATION_AMOUNT: Balance = MIN_STAKE_AMOUNT;
+
+    /// Bound on distinct delegators per agent, so `distribute_agent_fee`'s
+    /// per-query payout loop stays a fixed cost
+    const MAX_DELEGATORS_PER_AGENT: usize = 100;
+
+    /// Maximum fraction (%) of the cluster's total activating/deactivating
+    /// stake that can warm up or cool down in a single epoch
+    const WARMUP_COOLDOWN_RATE_PCT: u128 = 25;
+
+    /// Bounded number of epochs of stake history retained on-chain
+    const MAX_STAKE_HISTORY_ENTRIES: usize = 64;
+
+    /// Bound on how many epochs of warmup/cooldown `effective_stake` will
+    /// replay before treating the residual as fully crossed over; keeps the
+    /// replay (and its per-epoch history scan) a fixed cost regardless of
+    /// how long ago an agent activated or started deactivating
+    const MAX_WARMUP_COOLDOWN_EPOCHS: u64 = 16;
+
+    /// Outcome of a reward redemption attempt, mirroring the stake program's
+    /// zero-point / zero-point-value skip conditions
+    enum RedeemOutcome {
+        ZeroPoints,
+        ZeroPointValue,
+        Redeemed { points: u128, amount: Balance },
+    }
+
+    /// Snapshot of the cluster's stake totals at a given epoch, mirroring
+    /// the stake program's `StakeHistory` sysvar
+    #[derive(Debug, scale::Encode, scale::Decode, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct StakeHistoryEntry {
+        epoch: u64,
+        total_activating: Balance,
+        total_deactivating: Balance,
+        total_effective: Balance,
     }
 
     #[derive(Debug, scale::Encode, scale::Decode, PartialEq, Eq, Clone)]
@@ -97,16 +199,75 @@ mod polka_agents {
         stake_amount: Balance,
         active: bool,
         created_at: u64,
+        // Total stake backing this agent from delegators (excludes owner stake)
+        delegated_stake: Balance,
+        // Share (0-100) of delegators' reward portion the owner keeps as commission
+        commission: u8,
+        // Reward credits accumulated since the last redemption
+        credits: u64,
+        // Reward points accumulated since the last redemption, using the
+        // same effective-stake snapshot as each contribution to the global
+        // `total_points` so a redemption never claims more than it earned
+        pending_points: u128,
+        // Epoch at which this agent last redeemed its reward pool share
+        last_redeemed_epoch: u64,
+        // Epoch from which this agent's stake began warming up
+        activation_epoch: u64,
+        // Epoch from which this agent's stake began cooling down
+        // (`NOT_DEACTIVATING` while the agent is not withdrawing)
+        deactivation_epoch: u64,
+        // Lifetime query fees earned (agent_fee, before owner/delegator split)
+        total_fees_earned: Balance,
+        // Lifetime platform fees paid out of this agent's queries
+        total_platform_fees_paid: Balance,
+        // Lifetime commission earned from delegators
+        total_commission_earned: Balance,
+        completed_count: u32,
+        failed_count: u32,
+        disputed_count: u32,
+    }
+
+    /// Full economic breakdown for a single agent
+    #[derive(Debug, scale::Encode, scale::Decode, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct AgentEarnings {
+        total_fees_earned: Balance,
+        total_platform_fees_paid: Balance,
+        total_commission_earned: Balance,
+        pending_reward_points: u128,
+        effective_stake: Balance,
+        completed_interactions: u32,
+        failed_interactions: u32,
+        disputed_interactions: u32,
+    }
+
+    /// A delegator's current stake and earnings against one agent
+    #[derive(Debug, scale::Encode, scale::Decode, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct DelegatorPosition {
+        delegated_amount: Balance,
+        // Share of the agent's total (owner + delegator) effective stake,
+        // in basis points (0-10_000)
+        share_bps: u32,
+        // Lifetime rewards paid out to this delegator by this agent (paid
+        // immediately at query time; there is no separate redemption step
+        // for delegators, so nothing is ever "accrued but unredeemed")
+        lifetime_rewards: Balance,
     }
 
     impl PolkaAgents {
         #[ink(constructor)]
-        pub fn new(platform_fee_percentage: u8) -> Self {
+        pub fn new(
+            platform_fee_percentage: u8,
+            response_window_ms: u64,
+            slash_penalty_percentage: u8,
+        ) -> Self {
             let owner = Self::env().caller();
-            
+
             // Validate fee percentage (must be between 0 and 100)
             assert!(platform_fee_percentage <= 100, "Fee percentage must be between 0 and 100");
-            
+            assert!(slash_penalty_percentage <= 100, "Slash penalty must be between 0 and 100");
+
             Self {
                 agent_counter: 1,  // Start from 1
                 interaction_counter: 1,
@@ -116,7 +277,166 @@ mod polka_agents {
                 agent_interactions: Mapping::default(),
                 platform_fee_percentage,
                 owner,
+                delegations: Mapping::default(),
+                agent_delegators: Mapping::default(),
+                reward_pool: 0,
+                total_points: 0,
+                total_credits: 0,
+                total_activating_stake: 0,
+                total_deactivating_stake: 0,
+                stake_history: Vec::new(),
+                response_window_ms,
+                slash_penalty_percentage,
+                delegator_rewards: Mapping::default(),
+            }
+        }
+
+        /// Current epoch, derived from the block timestamp
+        fn current_epoch(&self) -> u64 {
+            self.env().block_timestamp() / EPOCH_DURATION_MS
+        }
+
+        /// Record (or update) the cluster-wide stake totals for `epoch`
+        fn record_stake_history(&mut self, epoch: u64) {
+            if let Some(last) = self.stake_history.last() {
+                if last.epoch == epoch {
+                    let total_effective = last.total_effective;
+                    let len = self.stake_history.len();
+                    self.stake_history[len - 1] = StakeHistoryEntry {
+                        epoch,
+                        total_activating: self.total_activating_stake,
+                        total_deactivating: self.total_deactivating_stake,
+                        total_effective,
+                    };
+                    return;
+                }
+            }
+
+            let prev_effective = self.stake_history.last().map_or(0, |e| e.total_effective);
+            let warmed = self.total_activating_stake * WARMUP_COOLDOWN_RATE_PCT / 100;
+            let cooled = self.total_deactivating_stake * WARMUP_COOLDOWN_RATE_PCT / 100;
+            let total_effective = prev_effective.saturating_add(warmed).saturating_sub(cooled);
+
+            self.stake_history.push(StakeHistoryEntry {
+                epoch,
+                total_activating: self.total_activating_stake,
+                total_deactivating: self.total_deactivating_stake,
+                total_effective,
+            });
+
+            if self.stake_history.len() > MAX_STAKE_HISTORY_ENTRIES {
+                self.stake_history.remove(0);
+            }
+        }
+
+        /// Most recent recorded history entry at or before `epoch`
+        fn history_entry_at(&self, epoch: u64) -> Option<&StakeHistoryEntry> {
+            self.stake_history.iter().rev().find(|e| e.epoch <= epoch)
+        }
+
+        fn activating_stake_at(&self, epoch: u64) -> Balance {
+            self.history_entry_at(epoch)
+                .map_or(self.total_activating_stake, |e| e.total_activating)
+        }
+
+        fn deactivating_stake_at(&self, epoch: u64) -> Balance {
+            self.history_entry_at(epoch)
+                .map_or(self.total_deactivating_stake, |e| e.total_deactivating)
+        }
+
+        /// Cluster-wide effective stake at `epoch`, the fixed pool that
+        /// bounds how much can cross over cluster-wide this epoch
+        fn cluster_effective_stake_at(&self, epoch: u64) -> Balance {
+            self.history_entry_at(epoch).map_or(0, |e| e.total_effective)
+        }
+
+        /// Compute `agent`'s effective stake at `current_epoch`, replaying
+        /// the warmup (and, once deactivating, cooldown) schedule epoch by
+        /// epoch: each epoch at most `WARMUP_COOLDOWN_RATE_PCT`% of the
+        /// cluster's total effective stake can cross over cluster-wide,
+        /// split across stakers by their share of the activating (or
+        /// deactivating) total, except that each staker crosses at least
+        /// 1 unit per epoch even when that pool floors to zero.
+        fn effective_stake(&self, agent: &Agent, current_epoch: u64) -> Balance {
+            if current_epoch <= agent.activation_epoch {
+                return 0;
+            }
+
+            let mut remaining_to_activate = agent.stake_amount;
+            let mut effective: Balance = 0;
+
+            // Integer-floor warmup increments shrink to the 1-unit minimum
+            // once the remainder is small, so the replay is capped at a bounded
+            // lookback rather than the (unbounded, ever-growing)
+            // `activation_epoch..current_epoch` range; whatever hasn't
+            // crossed over by then is treated as fully activated, matching
+            // real-world warmup converging within a handful of epochs.
+            let warmup_cutoff = agent.activation_epoch.saturating_add(MAX_WARMUP_COOLDOWN_EPOCHS);
+            let warmup_end = warmup_cutoff.min(current_epoch);
+
+            for epoch in agent.activation_epoch..warmup_end {
+                if remaining_to_activate == 0 {
+                    break;
+                }
+
+                let cluster_activating = self.activating_stake_at(epoch);
+                if cluster_activating == 0 {
+                    effective += remaining_to_activate;
+                    remaining_to_activate = 0;
+                    break;
+                }
+
+                let warmup_pool = self.cluster_effective_stake_at(epoch) * WARMUP_COOLDOWN_RATE_PCT / 100;
+                let newly_effective = (warmup_pool * remaining_to_activate / cluster_activating)
+                    .max(1)
+                    .min(remaining_to_activate);
+                effective += newly_effective;
+                remaining_to_activate -= newly_effective;
+            }
+
+            // Only force-convert whatever hasn't crossed over once the
+            // bounded lookback itself (not just `current_epoch`) has been
+            // reached — otherwise a still-within-window remainder would be
+            // credited as effective before it has actually warmed up.
+            if current_epoch >= warmup_cutoff {
+                effective += remaining_to_activate;
+            }
+
+            if agent.deactivation_epoch == NOT_DEACTIVATING || current_epoch <= agent.deactivation_epoch {
+                return effective;
             }
+
+            let mut remaining_to_deactivate = effective;
+            let cooldown_cutoff = agent.deactivation_epoch.saturating_add(MAX_WARMUP_COOLDOWN_EPOCHS);
+            let cooldown_end = cooldown_cutoff.min(current_epoch);
+
+            for epoch in agent.deactivation_epoch..cooldown_end {
+                if remaining_to_deactivate == 0 {
+                    break;
+                }
+
+                let cluster_deactivating = self.deactivating_stake_at(epoch);
+                if cluster_deactivating == 0 {
+                    effective = 0;
+                    remaining_to_deactivate = 0;
+                    break;
+                }
+
+                let cooldown_pool = self.cluster_effective_stake_at(epoch) * WARMUP_COOLDOWN_RATE_PCT / 100;
+                let newly_deactivated = (cooldown_pool * remaining_to_deactivate / cluster_deactivating)
+                    .max(1)
+                    .min(remaining_to_deactivate);
+                effective -= newly_deactivated;
+                remaining_to_deactivate -= newly_deactivated;
+            }
+
+            // Only force-convert the residual once the bounded lookback
+            // itself has been reached, mirroring the warmup-side gating
+            if current_epoch >= cooldown_cutoff {
+                effective -= remaining_to_deactivate;
+            }
+
+            effective
         }
 
         /// Register a new AI agent
@@ -130,15 +450,16 @@ mod polka_agents {
             let stake_amount = self.env().transferred_value();
             
             // Ensure minimum stake amount (can be adjusted)
-            if stake_amount < 10 {
+            if stake_amount < MIN_STAKE_AMOUNT {
                 return Err(Error::InvalidStakeAmount);
             }
-            
+
             let agent_id = self.agent_counter;
             self.agent_counter += 1;
-            
+
             let current_time = self.env().block_timestamp();
-            
+            let current_epoch = self.current_epoch();
+
             // Create new agent
             let agent = Agent {
                 id: agent_id,
@@ -148,11 +469,28 @@ mod polka_agents {
                 stake_amount,
                 active: true,
                 created_at: current_time,
+                delegated_stake: 0,
+                commission: 0,
+                credits: 0,
+                pending_points: 0,
+                last_redeemed_epoch: current_epoch,
+                activation_epoch: current_epoch,
+                deactivation_epoch: NOT_DEACTIVATING,
+                total_fees_earned: 0,
+                total_platform_fees_paid: 0,
+                total_commission_earned: 0,
+                completed_count: 0,
+                failed_count: 0,
+                disputed_count: 0,
             };
-            
+
             // Store the agent
             self.agents.insert(agent_id, &agent);
-            
+
+            // The newly-staked amount enters warmup this epoch
+            self.total_activating_stake += stake_amount;
+            self.record_stake_history(current_epoch);
+
             // Emit event
             self.env().emit_event(AgentRegistered {
                 agent_id,
@@ -172,30 +510,38 @@ mod polka_agents {
             metadata: Option<AgentMetadata>,
             price_per_query: Option<Balance>,
             active: Option<bool>,
+            commission: Option<u8>,
         ) -> Result<(), Error> {
             let caller = self.env().caller();
-            
+
             // Get the agent, return error if not found
             let mut agent = self.agents.get(agent_id).ok_or(Error::AgentNotFound)?;
-            
+
             // Check if caller is the owner
             if agent.owner != caller {
                 return Err(Error::UnauthorizedOwner);
             }
-            
+
             // Update fields if provided
             if let Some(new_metadata) = metadata {
                 agent.metadata = new_metadata;
             }
-            
+
             if let Some(new_price) = price_per_query {
                 agent.price_per_query = new_price;
             }
-            
+
             if let Some(new_active) = active {
                 agent.active = new_active;
             }
-            
+
+            if let Some(new_commission) = commission {
+                if new_commission > 100 {
+                    return Err(Error::InvalidCommission);
+                }
+                agent.commission = new_commission;
+            }
+
             // Store updated agent
             self.agents.insert(agent_id, &agent);
             
@@ -214,6 +560,48 @@ mod polka_agents {
             self.agents.get(agent_id)
         }
 
+        /// Full economic breakdown for an agent: lifetime fees, commission,
+        /// pending redeemable reward points, current effective stake, and
+        /// interaction outcome counts
+        #[ink(message)]
+        pub fn get_agent_earnings(&self, agent_id: AgentId) -> Option<AgentEarnings> {
+            let agent = self.agents.get(agent_id)?;
+            let effective_stake = self.effective_stake(&agent, self.current_epoch());
+
+            Some(AgentEarnings {
+                total_fees_earned: agent.total_fees_earned,
+                total_platform_fees_paid: agent.total_platform_fees_paid,
+                total_commission_earned: agent.total_commission_earned,
+                pending_reward_points: agent.pending_points,
+                effective_stake,
+                completed_interactions: agent.completed_count,
+                failed_interactions: agent.failed_count,
+                disputed_interactions: agent.disputed_count,
+            })
+        }
+
+        /// A delegator's current stake, pro-rata share, and lifetime
+        /// rewards against a given agent
+        #[ink(message)]
+        pub fn get_delegator_position(&self, agent_id: AgentId, who: AccountId) -> Option<DelegatorPosition> {
+            let agent = self.agents.get(agent_id)?;
+            let delegated_amount = self.delegations.get((agent_id, who)).unwrap_or(0);
+
+            let owner_stake = self.effective_stake(&agent, self.current_epoch());
+            let total_stake = owner_stake + agent.delegated_stake;
+            let share_bps = if total_stake == 0 {
+                0
+            } else {
+                (delegated_amount * 10_000 / total_stake) as u32
+            };
+
+            Some(DelegatorPosition {
+                delegated_amount,
+                share_bps,
+                lifetime_rewards: self.delegator_rewards.get((agent_id, who)).unwrap_or(0),
+            })
+        }
+
         /// Query an agent (pay fee)
         #[ink(message, payable)]
         pub fn query_agent(
@@ -225,30 +613,37 @@ mod polka_agents {
             let payment = self.env().transferred_value();
             
             // Get the agent, return error if not found
-            let agent = self.agents.get(agent_id).ok_or(Error::AgentNotFound)?;
-            
-            // Check if agent is active
-            if !agent.active {
+            let mut agent = self.agents.get(agent_id).ok_or(Error::AgentNotFound)?;
+
+            // Check if agent is active and has warmed up enough effective
+            // stake to be eligible (prevents register -> burst of queries
+            // -> immediate withdraw)
+            if !agent.active || self.effective_stake(&agent, self.current_epoch()) < MIN_STAKE_AMOUNT {
                 return Err(Error::AgentNotActive);
             }
-            
+
             // Check if payment is sufficient
             if payment < agent.price_per_query {
                 return Err(Error::InsufficientPayment);
             }
-            
-            // Calculate platform fee
+
+            // Calculate platform fee and feed it into the reward pool
             let platform_fee = payment * self.platform_fee_percentage as u128 / 100;
             let agent_fee = payment - platform_fee;
-            
-            // Transfer fee to agent owner (minus platform fee)
+            self.reward_pool += platform_fee;
+
+            agent.total_fees_earned += agent_fee;
+            agent.total_platform_fees_paid += platform_fee;
+
+            // Split the agent fee between the owner and delegators, pro-rata
+            // by stake, with the owner taking `commission`% of the
+            // delegators' share before the pro-rata split.
             if agent_fee > 0 {
-                if self.env().transfer(agent.owner, agent_fee).is_err() {
-                    // Handle transfer error (in a real implementation)
-                    // For simplicity, we continue anyway
-                }
+                self.distribute_agent_fee(&mut agent, agent_id, agent_fee);
             }
-            
+
+            self.agents.insert(agent_id, &agent);
+
             // Generate interaction ID
             let interaction_id = self.interaction_counter;
             self.interaction_counter += 1;
@@ -265,6 +660,7 @@ mod polka_agents {
                 timestamp: current_time,
                 status: InteractionStatus::Pending,
                 fee_paid: payment,
+                response_deadline: current_time + self.response_window_ms,
             };
             
             // Store the interaction
@@ -291,43 +687,270 @@ mod polka_agents {
             Ok(interaction_id)
         }
 
+        /// Split `agent_fee` between the agent owner and its delegators in
+        /// proportion to (owner stake + each delegator's stake), taking the
+        /// owner's `commission`% cut of the delegators' share first.
+        fn distribute_agent_fee(&mut self, agent: &mut Agent, agent_id: AgentId, agent_fee: Balance) {
+            let owner_stake = self.effective_stake(agent, self.current_epoch());
+            let total_stake = owner_stake + agent.delegated_stake;
+            if total_stake == 0 {
+                return;
+            }
+
+            let delegators_share =
+                agent_fee * agent.delegated_stake / total_stake;
+            let owner_base_share = agent_fee - delegators_share;
+            let commission_amount = delegators_share * agent.commission as u128 / 100;
+            let owner_share = owner_base_share + commission_amount;
+            let remaining_delegators_share = delegators_share - commission_amount;
+
+            if owner_share > 0 && self.env().transfer(agent.owner, owner_share).is_ok() {
+                agent.total_commission_earned += commission_amount;
+
+                self.env().emit_event(RewardDistributed {
+                    agent_id,
+                    recipient: agent.owner,
+                    amount: owner_share,
+                });
+            }
+
+            if remaining_delegators_share == 0 || agent.delegated_stake == 0 {
+                return;
+            }
+
+            let delegators = self.agent_delegators.get(agent_id).unwrap_or_default();
+            for delegator in delegators {
+                let stake = self.delegations.get((agent_id, delegator)).unwrap_or(0);
+                if stake == 0 {
+                    continue;
+                }
+                let share = remaining_delegators_share * stake / agent.delegated_stake;
+                if share > 0 && self.env().transfer(delegator, share).is_ok() {
+                    let key = (agent_id, delegator);
+                    let lifetime = self.delegator_rewards.get(key).unwrap_or(0);
+                    self.delegator_rewards.insert(key, &(lifetime + share));
+
+                    self.env().emit_event(RewardDistributed {
+                        agent_id,
+                        recipient: delegator,
+                        amount: share,
+                    });
+                }
+            }
+        }
+
+        /// Delegate stake to an agent, backing it with funds in exchange for
+        /// a pro-rata share of its query revenue
+        #[ink(message, payable)]
+        pub fn delegate_to_agent(&mut self, agent_id: AgentId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let amount = self.env().transferred_value();
+
+            if amount < MIN_DELEGATION_AMOUNT {
+                return Err(Error::InvalidDelegationAmount);
+            }
+
+            let mut agent = self.agents.get(agent_id).ok_or(Error::AgentNotFound)?;
+
+            let key = (agent_id, caller);
+            let existing = self.delegations.get(key).unwrap_or(0);
+
+            if existing == 0 {
+                let mut delegators = self.agent_delegators.get(agent_id).unwrap_or_default();
+                if delegators.len() >= MAX_DELEGATORS_PER_AGENT {
+                    return Err(Error::TooManyDelegators);
+                }
+                delegators.push(caller);
+                self.agent_delegators.insert(agent_id, &delegators);
+            }
+
+            self.delegations.insert(key, &(existing + amount));
+
+            agent.delegated_stake += amount;
+            self.agents.insert(agent_id, &agent);
+
+            self.env().emit_event(Delegated {
+                agent_id,
+                delegator: caller,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Withdraw a previously delegated stake from an agent
+        #[ink(message)]
+        pub fn undelegate(&mut self, agent_id: AgentId, amount: Balance) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let mut agent = self.agents.get(agent_id).ok_or(Error::AgentNotFound)?;
+
+            let key = (agent_id, caller);
+            let existing = self.delegations.get(key).ok_or(Error::DelegationNotFound)?;
+
+            if amount > existing {
+                return Err(Error::InsufficientDelegation);
+            }
+
+            let remaining = existing - amount;
+            if remaining > 0 && remaining < MIN_DELEGATION_AMOUNT {
+                return Err(Error::InvalidDelegationAmount);
+            }
+
+            if remaining == 0 {
+                self.delegations.remove(key);
+                let mut delegators = self.agent_delegators.get(agent_id).unwrap_or_default();
+                delegators.retain(|d| d != &caller);
+                self.agent_delegators.insert(agent_id, &delegators);
+            } else {
+                self.delegations.insert(key, &remaining);
+            }
+
+            agent.delegated_stake -= amount;
+            self.agents.insert(agent_id, &agent);
+
+            if amount > 0 {
+                if self.env().transfer(caller, amount).is_err() {
+                    // Handle transfer error (in a real implementation)
+                    // For simplicity, we continue anyway
+                }
+            }
+
+            self.env().emit_event(Undelegated {
+                agent_id,
+                delegator: caller,
+                amount,
+            });
+
+            Ok(())
+        }
+
         /// Submit response to a query
         #[ink(message)]
         pub fn submit_response(
             &mut self,
             interaction_id: InteractionId,
             response_data: Vec<u8>,
+            success: bool,
         ) -> Result<(), Error> {
             let caller = self.env().caller();
-            
+
             // Get the interaction
             let mut interaction = self.interactions.get(interaction_id).ok_or(Error::InteractionNotFound)?;
-            
+
             // Get the agent
-            let agent = self.agents.get(interaction.agent_id).ok_or(Error::AgentNotFound)?;
-            
+            let mut agent = self.agents.get(interaction.agent_id).ok_or(Error::AgentNotFound)?;
+
             // Check if caller is the agent owner
             if agent.owner != caller {
                 return Err(Error::UnauthorizedOwner);
             }
-            
-            // Update interaction with response
+
+            if interaction.status != InteractionStatus::Pending {
+                return Err(Error::InteractionNotPending);
+            }
+
             interaction.response_data = Some(response_data);
+
+            if !success {
+                // No valid answer to deliver; refund the user out of the
+                // agent's stake instead of its (already-distributed) fee
+                interaction.status = InteractionStatus::Failed;
+                self.interactions.insert(interaction_id, &interaction);
+
+                self.refund_from_stake(&mut agent, interaction.fee_paid, interaction.user);
+                agent.failed_count += 1;
+                self.agents.insert(interaction.agent_id, &agent);
+
+                self.env().emit_event(ResponseSubmitted {
+                    interaction_id,
+                    agent_id: interaction.agent_id,
+                    user: interaction.user,
+                });
+                return Ok(());
+            }
+
             interaction.status = InteractionStatus::Completed;
-            
+
             // Store updated interaction
             self.interactions.insert(interaction_id, &interaction);
-            
+
+            // Accrue reward credits for the agent, weighted by the fee paid.
+            // The fee is a u128 `Balance`; saturate rather than truncate so a
+            // fee above `u64::MAX` can't silently wrap the credit weight.
+            let credit_weight = interaction.fee_paid.min(u64::MAX as Balance) as u64;
+            agent.credits = agent.credits.saturating_add(credit_weight);
+            agent.completed_count += 1;
+
+            let effective_stake = self.effective_stake(&agent, self.current_epoch());
+            let point_delta = effective_stake * credit_weight as u128;
+            // Snapshot the same point delta on the agent as is added to the
+            // global total, so a later redemption can never claim more
+            // points than this agent actually contributed.
+            agent.pending_points = agent.pending_points.saturating_add(point_delta);
+            self.agents.insert(interaction.agent_id, &agent);
+
+            self.total_credits = self.total_credits.saturating_add(credit_weight);
+            self.total_points = self.total_points.saturating_add(point_delta);
+
             // Emit event
             self.env().emit_event(ResponseSubmitted {
                 interaction_id,
                 agent_id: interaction.agent_id,
                 user: interaction.user,
             });
-            
+
             Ok(())
         }
 
+        /// Reduce the cluster-wide activating/deactivating total that
+        /// `raw_amount` leaving `agent`'s stake (via refund or slash) is
+        /// drawn from, called before `agent.stake_amount` is decremented.
+        /// Re-snapshots `stake_history` immediately so the reduction is
+        /// visible to other agents' `effective_stake` replay for this epoch.
+        fn reduce_cluster_stake(&mut self, agent: &Agent, raw_amount: Balance, current_epoch: u64) {
+            if raw_amount == 0 {
+                return;
+            }
+
+            if agent.deactivation_epoch == NOT_DEACTIVATING {
+                self.total_activating_stake = self.total_activating_stake.saturating_sub(raw_amount);
+            } else {
+                let effective = self.effective_stake(agent, current_epoch);
+                let reduction = if agent.stake_amount == 0 {
+                    0
+                } else {
+                    effective * raw_amount / agent.stake_amount
+                };
+                self.total_deactivating_stake = self.total_deactivating_stake.saturating_sub(reduction);
+            }
+
+            self.record_stake_history(current_epoch);
+        }
+
+        /// Refund `amount` to `recipient` out of `agent`'s stake, capped at
+        /// what's available, deactivating the agent if its stake then falls
+        /// below the minimum. Returns the amount actually refunded.
+        fn refund_from_stake(&mut self, agent: &mut Agent, amount: Balance, recipient: AccountId) -> Balance {
+            let refund = amount.min(agent.stake_amount);
+            let current_epoch = self.current_epoch();
+            self.reduce_cluster_stake(agent, refund, current_epoch);
+            agent.stake_amount -= refund;
+
+            if refund > 0 {
+                if self.env().transfer(recipient, refund).is_err() {
+                    // Handle transfer error (in a real implementation)
+                    // For simplicity, we continue anyway
+                }
+            }
+
+            if agent.stake_amount < MIN_STAKE_AMOUNT {
+                agent.active = false;
+            }
+
+            refund
+        }
+
         /// Get an interaction
         #[ink(message)]
         pub fn get_interaction(&self, interaction_id: InteractionId) -> Option<Interaction> {
@@ -363,46 +986,220 @@ mod polka_agents {
             
             // Update fee
             self.platform_fee_percentage = new_fee_percentage;
-            
+
+            Ok(())
+        }
+
+        /// Update the dispute response window (in milliseconds)
+        #[ink(message)]
+        pub fn update_response_window(&mut self, new_window_ms: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            if caller != self.owner {
+                return Err(Error::UnauthorizedOwner);
+            }
+
+            self.response_window_ms = new_window_ms;
+
             Ok(())
         }
 
-        /// Withdraw stake (deactivate agent)
+        /// Update the stake slash penalty percentage applied on disputes
+        #[ink(message)]
+        pub fn update_slash_penalty(&mut self, new_percentage: u8) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            if caller != self.owner {
+                return Err(Error::UnauthorizedOwner);
+            }
+
+            if new_percentage > 100 {
+                return Err(Error::InvalidFeePercentage);
+            }
+
+            self.slash_penalty_percentage = new_percentage;
+
+            Ok(())
+        }
+
+        /// Withdraw stake. The first call starts the stake cooling down;
+        /// it's only transferred back once it has fully cooled (effective
+        /// stake reaches zero), mirroring the stake program's lockup.
         #[ink(message)]
         pub fn withdraw_stake(&mut self, agent_id: AgentId) -> Result<(), Error> {
             let caller = self.env().caller();
-            
+
             // Get the agent
             let mut agent = self.agents.get(agent_id).ok_or(Error::AgentNotFound)?;
-            
+
             // Check if caller is the owner
             if agent.owner != caller {
                 return Err(Error::UnauthorizedOwner);
             }
-            
-            // Deactivate agent
-            agent.active = false;
-            
+
+            let current_epoch = self.current_epoch();
+
+            if agent.deactivation_epoch == NOT_DEACTIVATING {
+                // Start cooling down the currently-effective stake
+                agent.active = false;
+                agent.deactivation_epoch = current_epoch;
+
+                let effective = self.effective_stake(&agent, current_epoch);
+                self.total_activating_stake = self.total_activating_stake.saturating_sub(agent.stake_amount);
+                self.total_deactivating_stake += effective;
+
+                self.agents.insert(agent_id, &agent);
+                self.record_stake_history(current_epoch);
+
+                self.env().emit_event(StakeWithdrawn {
+                    agent_id,
+                    owner: caller,
+                });
+
+                return Ok(());
+            }
+
+            // Already cooling down; only payable out once fully cooled
+            if self.effective_stake(&agent, current_epoch) > 0 {
+                return Err(Error::StakeCoolingDown);
+            }
+
             // Transfer stake back to owner
             if agent.stake_amount > 0 {
                 let stake = agent.stake_amount;
                 agent.stake_amount = 0;
-                
+
                 if self.env().transfer(caller, stake).is_err() {
                     // Handle transfer error
                     // For simplicity, we continue anyway
                 }
             }
-            
+
             // Update agent
             self.agents.insert(agent_id, &agent);
-            
+
             // Emit event
             self.env().emit_event(StakeWithdrawn {
                 agent_id,
                 owner: caller,
             });
-            
+
+            Ok(())
+        }
+
+        /// Redeem an agent's accrued reward-pool share, valued at
+        /// `reward_pool / total_points` per pending point. A no-op when the
+        /// agent has zero points or the pool values points at zero.
+        #[ink(message)]
+        pub fn redeem_rewards(&mut self, agent_id: AgentId) -> Result<(), Error> {
+            let mut agent = self.agents.get(agent_id).ok_or(Error::AgentNotFound)?;
+
+            match self.compute_redemption(&agent) {
+                RedeemOutcome::ZeroPoints | RedeemOutcome::ZeroPointValue => Ok(()),
+                RedeemOutcome::Redeemed { points, amount: reward } => {
+                    self.total_points = self.total_points.saturating_sub(points);
+                    self.total_credits = self.total_credits.saturating_sub(agent.credits);
+                    self.reward_pool = self.reward_pool.saturating_sub(reward);
+
+                    agent.credits = 0;
+                    agent.pending_points = 0;
+                    agent.last_redeemed_epoch = self.current_epoch();
+                    self.agents.insert(agent_id, &agent);
+
+                    if self.env().transfer(agent.owner, reward).is_err() {
+                        // Handle transfer error (in a real implementation)
+                        // For simplicity, we continue anyway
+                    }
+
+                    self.env().emit_event(RewardsRedeemed {
+                        agent_id,
+                        points,
+                        amount: reward,
+                    });
+
+                    Ok(())
+                }
+            }
+        }
+
+        /// Work out the redemption outcome for `agent` without mutating state
+        fn compute_redemption(&self, agent: &Agent) -> RedeemOutcome {
+            let points = agent.pending_points;
+            if points == 0 {
+                return RedeemOutcome::ZeroPoints;
+            }
+
+            if self.total_points == 0 {
+                return RedeemOutcome::ZeroPointValue;
+            }
+            let point_value = self.reward_pool / self.total_points;
+            if point_value == 0 {
+                return RedeemOutcome::ZeroPointValue;
+            }
+
+            RedeemOutcome::Redeemed {
+                points,
+                // Cap at the pool balance: `points` is drawn from the same
+                // snapshot basis as `total_points`, so this should already
+                // hold, but the pool is the hard ceiling on what can be paid.
+                amount: (points * point_value).min(self.reward_pool),
+            }
+        }
+
+        /// Raise a dispute on a `Pending` interaction whose response
+        /// deadline has passed. Refunds the paid fee and slashes an
+        /// additional penalty from the agent's stake into the reward pool,
+        /// using stake as collateral since the fee itself was already paid
+        /// out at query time.
+        #[ink(message)]
+        pub fn raise_dispute(&mut self, interaction_id: InteractionId) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let mut interaction = self.interactions.get(interaction_id).ok_or(Error::InteractionNotFound)?;
+
+            if interaction.user != caller {
+                return Err(Error::UnauthorizedUser);
+            }
+
+            if interaction.status != InteractionStatus::Pending {
+                return Err(Error::InteractionNotPending);
+            }
+
+            if self.env().block_timestamp() < interaction.response_deadline {
+                return Err(Error::DisputeWindowNotElapsed);
+            }
+
+            let mut agent = self.agents.get(interaction.agent_id).ok_or(Error::AgentNotFound)?;
+
+            interaction.status = InteractionStatus::Disputed;
+            self.interactions.insert(interaction_id, &interaction);
+
+            let refund = self.refund_from_stake(&mut agent, interaction.fee_paid, interaction.user);
+
+            let penalty = (agent.stake_amount * self.slash_penalty_percentage as u128 / 100)
+                .min(agent.stake_amount);
+            let current_epoch = self.current_epoch();
+            self.reduce_cluster_stake(&agent, penalty, current_epoch);
+            agent.stake_amount -= penalty;
+            self.reward_pool += penalty;
+            if agent.stake_amount < MIN_STAKE_AMOUNT {
+                agent.active = false;
+            }
+
+            agent.disputed_count += 1;
+            self.agents.insert(interaction.agent_id, &agent);
+
+            self.env().emit_event(DisputeRaised {
+                interaction_id,
+                agent_id: interaction.agent_id,
+                user: caller,
+                refund,
+            });
+            self.env().emit_event(StakeSlashed {
+                agent_id: interaction.agent_id,
+                amount: penalty,
+            });
+
             Ok(())
         }
     }
@@ -455,5 +1252,178 @@ mod polka_agents {
         owner: AccountId,
     }
 
-    // Unit tests are omitted for brevity
+    #[ink(event)]
+    pub struct Delegated {
+        #[ink(topic)]
+        agent_id: AgentId,
+        #[ink(topic)]
+        delegator: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Undelegated {
+        #[ink(topic)]
+        agent_id: AgentId,
+        #[ink(topic)]
+        delegator: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RewardDistributed {
+        #[ink(topic)]
+        agent_id: AgentId,
+        #[ink(topic)]
+        recipient: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RewardsRedeemed {
+        #[ink(topic)]
+        agent_id: AgentId,
+        points: u128,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DisputeRaised {
+        #[ink(topic)]
+        interaction_id: InteractionId,
+        #[ink(topic)]
+        agent_id: AgentId,
+        user: AccountId,
+        refund: Balance,
+    }
+
+    #[ink(event)]
+    pub struct StakeSlashed {
+        #[ink(topic)]
+        agent_id: AgentId,
+        amount: Balance,
+    }
+
+    // Unit tests for `effective_stake`'s warmup/cooldown replay only; the
+    // rest of the contract has no tests here. This math is pure `&self`
+    // logic over hand-built fixtures, and risky enough (money-weighted
+    // payouts) to be worth covering directly.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn test_agent(stake_amount: Balance, activation_epoch: u64, deactivation_epoch: u64) -> Agent {
+            Agent {
+                id: 1,
+                owner: AccountId::from([0u8; 32]),
+                metadata: AgentMetadata {
+                    name: String::new(),
+                    description: String::new(),
+                    agent_type: AgentType::Chatbot,
+                    model_info: String::new(),
+                },
+                price_per_query: 0,
+                stake_amount,
+                active: true,
+                created_at: 0,
+                delegated_stake: 0,
+                commission: 0,
+                credits: 0,
+                pending_points: 0,
+                last_redeemed_epoch: 0,
+                activation_epoch,
+                deactivation_epoch,
+                total_fees_earned: 0,
+                total_platform_fees_paid: 0,
+                total_commission_earned: 0,
+                completed_count: 0,
+                failed_count: 0,
+                disputed_count: 0,
+            }
+        }
+
+        fn contract_with_history(stake_history: Vec<StakeHistoryEntry>) -> PolkaAgents {
+            let (total_activating_stake, total_deactivating_stake) = stake_history
+                .last()
+                .map_or((0, 0), |e| (e.total_activating, e.total_deactivating));
+
+            PolkaAgents {
+                agent_counter: 1,
+                interaction_counter: 1,
+                agents: Mapping::default(),
+                interactions: Mapping::default(),
+                user_interactions: Mapping::default(),
+                agent_interactions: Mapping::default(),
+                platform_fee_percentage: 0,
+                owner: AccountId::from([0u8; 32]),
+                delegations: Mapping::default(),
+                agent_delegators: Mapping::default(),
+                reward_pool: 0,
+                total_points: 0,
+                total_credits: 0,
+                total_activating_stake,
+                total_deactivating_stake,
+                stake_history,
+                response_window_ms: 0,
+                slash_penalty_percentage: 0,
+                delegator_rewards: Mapping::default(),
+            }
+        }
+
+        fn history_entry(epoch: u64, total_activating: Balance, total_deactivating: Balance, total_effective: Balance) -> StakeHistoryEntry {
+            StakeHistoryEntry {
+                epoch,
+                total_activating,
+                total_deactivating,
+                total_effective,
+            }
+        }
+
+        #[test]
+        fn partial_warmup_is_bounded_by_share_of_cluster_effective_stake() {
+            // Cluster-wide: 200 activating, 40 already effective. This
+            // agent is half of the activating total, so it should only
+            // get half of this epoch's 40 * 25% = 10 warmup pool.
+            let contract = contract_with_history(vec![history_entry(0, 200, 0, 40)]);
+            let agent = test_agent(100, 0, NOT_DEACTIVATING);
+
+            assert_eq!(contract.effective_stake(&agent, 1), 5);
+        }
+
+        #[test]
+        fn warmup_forces_full_convergence_only_at_the_cutoff_epoch() {
+            // Sole staker, nothing else ever becomes effective, so each
+            // epoch's pool floors to the 1-unit minimum crossover.
+            let contract = contract_with_history(vec![history_entry(0, 100, 0, 0)]);
+            let agent = test_agent(100, 0, NOT_DEACTIVATING);
+
+            // One epoch short of the 16-epoch cutoff: only the 15 epochs'
+            // worth of 1-unit increments have crossed over, not the rest.
+            assert_eq!(contract.effective_stake(&agent, 15), 15);
+
+            // At the cutoff itself, whatever remains is force-converted.
+            assert_eq!(contract.effective_stake(&agent, 16), 100);
+        }
+
+        #[test]
+        fn cooldown_and_a_concurrent_warmup_share_the_same_cluster_effective_pool() {
+            // Agent `d` activated into an empty cluster (instant warmup)
+            // and starts cooling down at epoch 5, the same epoch agent `x`
+            // starts warming up. Both draw their epoch-5 crossover pool
+            // from the same recorded `total_effective` of 40.
+            let contract = contract_with_history(vec![
+                history_entry(0, 0, 0, 0),
+                history_entry(5, 300, 100, 40),
+            ]);
+            let d = test_agent(100, 0, 5);
+            let x = test_agent(300, 5, NOT_DEACTIVATING);
+
+            // d: sole occupant of the deactivating bucket, so it gets the
+            // full 40 * 25% = 10 cooldown pool this epoch.
+            assert_eq!(contract.effective_stake(&d, 6), 90);
+            // x: sole occupant of the activating bucket, so it likewise
+            // gets the full 10-unit warmup pool this epoch.
+            assert_eq!(contract.effective_stake(&x, 6), 10);
+        }
+    }
 }
\ No newline at end of file